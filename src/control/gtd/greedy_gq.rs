@@ -1,4 +1,5 @@
 use core::{Algorithm, Controller, Predictor, Shared, Parameter, Vector};
+use core::changepoint::ChangepointDetector;
 use domains::Transition;
 use fa::{Approximator, MultiLFA, Projection, Projector, QFunction, SimpleLFA, VFunction};
 use policies::{fixed::Greedy, Policy, FinitePolicy};
@@ -20,6 +21,9 @@ pub struct GreedyGQ<S, M: Projector<S>, P: Policy<S>> {
     pub beta: Parameter,
     pub gamma: Parameter,
 
+    alpha0: Parameter,
+    changepoint: Option<ChangepointDetector>,
+
     phantom: PhantomData<S>,
 }
 
@@ -37,6 +41,8 @@ impl<S: 'static, M: Projector<S> + 'static, P: Policy<S>> GreedyGQ<S, M, P> {
         T2: Into<Parameter>,
         T3: Into<Parameter>,
     {
+        let alpha = alpha.into();
+
         GreedyGQ {
             fa_theta: fa_theta.clone(),
             fa_w: fa_w,
@@ -44,13 +50,24 @@ impl<S: 'static, M: Projector<S> + 'static, P: Policy<S>> GreedyGQ<S, M, P> {
             policy: policy,
             target: Greedy::new(fa_theta),
 
-            alpha: alpha.into(),
+            alpha,
             beta: beta.into(),
             gamma: gamma.into(),
 
+            alpha0: alpha,
+            changepoint: None,
+
             phantom: PhantomData,
         }
     }
+
+    /// Attach a Bayesian online changepoint detector that watches the TD
+    /// error stream and resets `fa_w` and the step-size whenever it signals
+    /// a shift in the underlying MDP.
+    pub fn with_changepoint_detector(mut self, detector: ChangepointDetector) -> Self {
+        self.changepoint = Some(detector);
+        self
+    }
 }
 
 impl<S, M: Projector<S>, P: Policy<S, Action = usize>> Algorithm<S, usize> for GreedyGQ<S, M, P> {
@@ -73,6 +90,13 @@ impl<S, M: Projector<S>, P: Policy<S, Action = usize>> Algorithm<S, usize> for G
         let update_q = td_error * phi_s.clone() - self.gamma * td_estimate * phi_ns;
         let update_v = (td_error - td_estimate) * phi_s;
 
+        let changepoint = self.changepoint.as_mut().map_or(false, |cpd| cpd.step(td_error));
+
+        if changepoint {
+            self.fa_w.borrow_mut().approximator.weights.fill(0.0);
+            self.alpha = self.alpha0;
+        }
+
         self.fa_w.borrow_mut()
             .update_phi(&Projection::Dense(update_v), self.alpha * self.beta);
         self.fa_theta.borrow_mut()
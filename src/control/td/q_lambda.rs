@@ -1,4 +1,5 @@
 use core::{Algorithm, Controller, Predictor, Shared, Parameter, Vector, Trace};
+use core::changepoint::ChangepointDetector;
 use domains::Transition;
 use fa::{Approximator, MultiLFA, Projection, Projector, QFunction};
 use policies::{fixed::Greedy, Policy};
@@ -22,6 +23,9 @@ pub struct QLambda<S, M: Projector<S>, P: Policy<S>> {
     pub alpha: Parameter,
     pub gamma: Parameter,
 
+    alpha0: Parameter,
+    changepoint: Option<ChangepointDetector>,
+
     phantom: PhantomData<S>,
 }
 
@@ -37,6 +41,8 @@ impl<S: 'static, M: Projector<S> + 'static, P: Policy<S>> QLambda<S, M, P> {
         T1: Into<Parameter>,
         T2: Into<Parameter>,
     {
+        let alpha = alpha.into();
+
         QLambda {
             trace: trace,
 
@@ -45,12 +51,23 @@ impl<S: 'static, M: Projector<S> + 'static, P: Policy<S>> QLambda<S, M, P> {
             policy: policy,
             target: Greedy::new(fa_theta),
 
-            alpha: alpha.into(),
+            alpha,
             gamma: gamma.into(),
 
+            alpha0: alpha,
+            changepoint: None,
+
             phantom: PhantomData,
         }
     }
+
+    /// Attach a Bayesian online changepoint detector that watches the TD
+    /// error stream and resets the trace and step-size whenever it signals
+    /// a shift in the underlying MDP.
+    pub fn with_changepoint_detector(mut self, detector: ChangepointDetector) -> Self {
+        self.changepoint = Some(detector);
+        self
+    }
 }
 
 impl<S, M: Projector<S>, P: Policy<S, Action = usize>> Algorithm<S, usize> for QLambda<S, M, P> {
@@ -64,7 +81,12 @@ impl<S, M: Projector<S>, P: Policy<S, Action = usize>> Algorithm<S, usize> for Q
 
         let td_error = t.reward + self.gamma * nqs[self.target.sample(&ns)] - qs[t.action];
 
-        if t.action == self.target.sample(&s) {
+        let changepoint = self.changepoint.as_mut().map_or(false, |cpd| cpd.step(td_error));
+
+        if changepoint {
+            self.trace.decay(0.0);
+            self.alpha = self.alpha0;
+        } else if t.action == self.target.sample(&s) {
             let rate = self.trace.lambda.value() * self.gamma.value();
             self.trace.decay(rate);
         } else {
@@ -0,0 +1,143 @@
+use core::{Algorithm, Controller, Predictor, Shared, Parameter, Vector};
+use domains::Transition;
+use fa::tabular::{LegalActions, Table};
+use rand::{thread_rng, Rng};
+use std::hash::Hash;
+
+/// Exact tabular Watkins' Q-learning with legal-action masking and
+/// epsilon-greedy exploration.
+///
+/// Identical in spirit to `QLambda`, but backed by a `Table` rather than a
+/// `Projector`-based linear function approximator, so there is no
+/// projection or eligibility-trace machinery to configure. Greedy action
+/// selection (`pi`) and the bootstrap target both range only over the
+/// actions reported legal for the current state by `legal_actions`, so the
+/// agent never bootstraps off the value of an action it could not take.
+/// The behavior policy `mu` is epsilon-greedy over the same legal set, so a
+/// control loop driven by `mu` still explores; feed it a fixed `epsilon` of
+/// `0.0` (or swap in an external behavior policy) for pure greedy control.
+///
+/// # References
+/// - Watkins, C. J. C. H. (1989). Learning from Delayed Rewards. Ph.D.
+/// thesis, Cambridge University.
+/// - Watkins, C. J. C. H., Dayan, P. (1992). Q-learning. Machine Learning,
+/// 8:279–292.
+pub struct QLearning<S, L> {
+    pub fa_theta: Shared<Table<S>>,
+    pub legal_actions: L,
+
+    pub alpha: Parameter,
+    pub gamma: Parameter,
+    pub epsilon: Parameter,
+}
+
+impl<S, L> QLearning<S, L> {
+    pub fn new<T1, T2, T3>(
+        fa_theta: Shared<Table<S>>,
+        legal_actions: L,
+        alpha: T1,
+        gamma: T2,
+        epsilon: T3,
+    ) -> Self
+    where
+        T1: Into<Parameter>,
+        T2: Into<Parameter>,
+        T3: Into<Parameter>,
+    {
+        QLearning {
+            fa_theta,
+            legal_actions,
+
+            alpha: alpha.into(),
+            gamma: gamma.into(),
+            epsilon: epsilon.into(),
+        }
+    }
+}
+
+impl<S, L> QLearning<S, L>
+where
+    S: Clone + Hash + Eq,
+    L: LegalActions<S>,
+{
+    fn epsilon_greedy(&mut self, s: &S) -> usize {
+        let legal = self.legal_actions.legal_actions(s);
+        let mut rng = thread_rng();
+
+        if rng.gen::<f64>() < self.epsilon.value() {
+            legal[rng.gen_range(0, legal.len())]
+        } else {
+            let qs = self.fa_theta.borrow().evaluate(s).unwrap();
+
+            legal
+                .into_iter()
+                .max_by(|&a, &b| qs[a].partial_cmp(&qs[b]).unwrap())
+                .expect("legal_actions returned an empty action set")
+        }
+    }
+}
+
+impl<S, L> Algorithm<S, usize> for QLearning<S, L>
+where
+    S: Clone + Hash + Eq,
+    L: LegalActions<S>,
+{
+    fn handle_sample(&mut self, t: &Transition<S, usize>) {
+        let (s, ns) = (t.from.state(), t.to.state());
+
+        let qs = self.fa_theta.borrow().evaluate(s).unwrap();
+        let nqs = self.fa_theta.borrow().evaluate(ns).unwrap();
+
+        let max_nq = self.legal_actions
+            .legal_actions(ns)
+            .into_iter()
+            .map(|a| nqs[a])
+            .fold(::std::f64::MIN, f64::max);
+
+        let td_error = t.reward + self.gamma.value() * max_nq - qs[t.action];
+
+        self.fa_theta
+            .borrow_mut()
+            .update_action(s, t.action, self.alpha.value() * td_error);
+    }
+
+    fn handle_terminal(&mut self, _: &Transition<S, usize>) {
+        self.alpha = self.alpha.step();
+        self.gamma = self.gamma.step();
+        self.epsilon = self.epsilon.step();
+    }
+}
+
+impl<S, L> Controller<S, usize> for QLearning<S, L>
+where
+    S: Clone + Hash + Eq,
+    L: LegalActions<S>,
+{
+    fn pi(&mut self, s: &S) -> usize {
+        let qs = self.fa_theta.borrow().evaluate(s).unwrap();
+
+        self.legal_actions
+            .legal_actions(s)
+            .into_iter()
+            .max_by(|&a, &b| qs[a].partial_cmp(&qs[b]).unwrap())
+            .expect("legal_actions returned an empty action set")
+    }
+
+    fn mu(&mut self, s: &S) -> usize { self.epsilon_greedy(s) }
+}
+
+impl<S, L> Predictor<S, usize> for QLearning<S, L>
+where
+    S: Clone + Hash + Eq,
+    L: LegalActions<S>,
+{
+    fn v(&mut self, s: &S) -> f64 {
+        let a = self.pi(s);
+
+        self.qsa(s, a)
+    }
+
+    fn qs(&mut self, s: &S) -> Vector<f64> { self.fa_theta.borrow().evaluate(s).unwrap() }
+
+    fn qsa(&mut self, s: &S, a: usize) -> f64 { self.fa_theta.borrow().evaluate_action(s, a) }
+}
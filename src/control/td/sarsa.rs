@@ -0,0 +1,131 @@
+use core::{Algorithm, Controller, Predictor, Shared, Parameter, Vector};
+use domains::Transition;
+use fa::tabular::{LegalActions, Table};
+use rand::{thread_rng, Rng};
+use std::hash::Hash;
+
+/// Exact tabular SARSA with legal-action masking and epsilon-greedy
+/// exploration.
+///
+/// On-policy counterpart to `QLearning`: the bootstrap target is the value
+/// of the action the agent's own epsilon-greedy policy would actually take
+/// next, rather than the greedy maximum, and both of those selections are
+/// restricted to the actions reported legal for the current state.
+///
+/// # References
+/// - Rummery, G. A., Niranjan, M. (1994). On-Line Q-Learning Using
+/// Connectionist Systems. Technical report, Cambridge University.
+pub struct SARSA<S, L> {
+    pub fa_theta: Shared<Table<S>>,
+    pub legal_actions: L,
+
+    pub alpha: Parameter,
+    pub gamma: Parameter,
+    pub epsilon: Parameter,
+}
+
+impl<S, L> SARSA<S, L> {
+    pub fn new<T1, T2, T3>(
+        fa_theta: Shared<Table<S>>,
+        legal_actions: L,
+        alpha: T1,
+        gamma: T2,
+        epsilon: T3,
+    ) -> Self
+    where
+        T1: Into<Parameter>,
+        T2: Into<Parameter>,
+        T3: Into<Parameter>,
+    {
+        SARSA {
+            fa_theta,
+            legal_actions,
+
+            alpha: alpha.into(),
+            gamma: gamma.into(),
+            epsilon: epsilon.into(),
+        }
+    }
+}
+
+impl<S, L> SARSA<S, L>
+where
+    S: Clone + Hash + Eq,
+    L: LegalActions<S>,
+{
+    fn epsilon_greedy(&mut self, s: &S) -> usize {
+        let legal = self.legal_actions.legal_actions(s);
+        let mut rng = thread_rng();
+
+        if rng.gen::<f64>() < self.epsilon.value() {
+            legal[rng.gen_range(0, legal.len())]
+        } else {
+            let qs = self.fa_theta.borrow().evaluate(s).unwrap();
+
+            legal
+                .into_iter()
+                .max_by(|&a, &b| qs[a].partial_cmp(&qs[b]).unwrap())
+                .expect("legal_actions returned an empty action set")
+        }
+    }
+}
+
+impl<S, L> Algorithm<S, usize> for SARSA<S, L>
+where
+    S: Clone + Hash + Eq,
+    L: LegalActions<S>,
+{
+    fn handle_sample(&mut self, t: &Transition<S, usize>) {
+        let (s, ns) = (t.from.state(), t.to.state());
+
+        let qs = self.fa_theta.borrow().evaluate(s).unwrap();
+        let na = self.epsilon_greedy(ns);
+        let nqs = self.fa_theta.borrow().evaluate(ns).unwrap();
+
+        let td_error = t.reward + self.gamma.value() * nqs[na] - qs[t.action];
+
+        self.fa_theta
+            .borrow_mut()
+            .update_action(s, t.action, self.alpha.value() * td_error);
+    }
+
+    fn handle_terminal(&mut self, _: &Transition<S, usize>) {
+        self.alpha = self.alpha.step();
+        self.gamma = self.gamma.step();
+        self.epsilon = self.epsilon.step();
+    }
+}
+
+impl<S, L> Controller<S, usize> for SARSA<S, L>
+where
+    S: Clone + Hash + Eq,
+    L: LegalActions<S>,
+{
+    fn pi(&mut self, s: &S) -> usize {
+        let qs = self.fa_theta.borrow().evaluate(s).unwrap();
+
+        self.legal_actions
+            .legal_actions(s)
+            .into_iter()
+            .max_by(|&a, &b| qs[a].partial_cmp(&qs[b]).unwrap())
+            .expect("legal_actions returned an empty action set")
+    }
+
+    fn mu(&mut self, s: &S) -> usize { self.epsilon_greedy(s) }
+}
+
+impl<S, L> Predictor<S, usize> for SARSA<S, L>
+where
+    S: Clone + Hash + Eq,
+    L: LegalActions<S>,
+{
+    fn v(&mut self, s: &S) -> f64 {
+        let a = self.pi(s);
+
+        self.qsa(s, a)
+    }
+
+    fn qs(&mut self, s: &S) -> Vector<f64> { self.fa_theta.borrow().evaluate(s).unwrap() }
+
+    fn qsa(&mut self, s: &S, a: usize) -> f64 { self.fa_theta.borrow().evaluate_action(s, a) }
+}
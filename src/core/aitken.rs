@@ -0,0 +1,101 @@
+//! Aitken's delta-squared acceleration for convergent weight sequences.
+
+use core::Vector;
+
+/// Apply Aitken's delta-squared method to three successive terms of a
+/// fixed-point iteration, elementwise.
+///
+/// Given `w0`, `w1 = f(w0)` and `w2 = f(w1)`, the accelerated estimate is
+///
+/// ```text
+/// w' = w0 - (w1 - w0)^2 / (w2 - 2*w1 + w0)
+/// ```
+///
+/// Components whose denominator is near zero (the sequence has already
+/// converged in that component) fall back to the raw iterate `w2` rather
+/// than dividing by (near) zero.
+fn aitken_delta_squared(w0: &Vector<f64>, w1: &Vector<f64>, w2: &Vector<f64>) -> Vector<f64> {
+    w0.iter()
+        .zip(w1.iter())
+        .zip(w2.iter())
+        .map(|((&a, &b), &c)| {
+            let denom = c - 2.0 * b + a;
+
+            if denom.abs() < 1e-12 {
+                c
+            } else {
+                a - (b - a) * (b - a) / denom
+            }
+        })
+        .collect()
+}
+
+/// Iterator adapter that accelerates a sequence of fixed-point iterates
+/// (e.g. successive LSPE(lambda) weight vectors) using Aitken's
+/// delta-squared method, and stops once the iterates have converged to
+/// within a tolerance.
+pub struct AitkenAccelerate<I> {
+    inner: I,
+    tol: f64,
+    window: Vec<Vector<f64>>,
+    done: bool,
+}
+
+impl<I: Iterator<Item = Vector<f64>>> AitkenAccelerate<I> {
+    pub fn new(inner: I, tol: f64) -> AitkenAccelerate<I> {
+        AitkenAccelerate {
+            inner,
+            tol,
+            window: Vec::with_capacity(3),
+            done: false,
+        }
+    }
+}
+
+impl<I: Iterator<Item = Vector<f64>>> Iterator for AitkenAccelerate<I> {
+    type Item = Vector<f64>;
+
+    fn next(&mut self) -> Option<Vector<f64>> {
+        if self.done {
+            return None;
+        }
+
+        while self.window.len() < 3 {
+            match self.inner.next() {
+                Some(w) => self.window.push(w),
+                None => {
+                    self.done = true;
+
+                    return self.window.pop();
+                },
+            }
+        }
+
+        let accelerated =
+            aitken_delta_squared(&self.window[0], &self.window[1], &self.window[2]);
+
+        let max_change = (&accelerated - &self.window[0])
+            .iter()
+            .cloned()
+            .fold(0.0, |acc, d| acc.max(d.abs()));
+
+        self.window.remove(0);
+
+        if max_change < self.tol {
+            self.done = true;
+        }
+
+        Some(accelerated)
+    }
+}
+
+/// Blanket adapter for any iterator of weight vectors produced by a
+/// fixed-point map, letting convergence acceleration be attached with
+/// `.aitken_accelerate(tol)`.
+pub trait ConvergentSequence: Iterator<Item = Vector<f64>> + Sized {
+    fn aitken_accelerate(self, tol: f64) -> AitkenAccelerate<Self> {
+        AitkenAccelerate::new(self, tol)
+    }
+}
+
+impl<I: Iterator<Item = Vector<f64>>> ConvergentSequence for I {}
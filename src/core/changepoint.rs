@@ -0,0 +1,209 @@
+//! Bayesian online changepoint detection.
+//!
+//! Gives trace-based controllers (`QLambda`, `GreedyGQ`, `LSTDLambda`) a way
+//! to notice that the underlying MDP has shifted and react by resetting
+//! their eligibility trace and/or step-size rather than continuing to trust
+//! statistics accumulated under the old regime.
+
+use std::f64::consts::PI;
+
+/// Sufficient statistics for a single run length under a Normal likelihood
+/// with a Normal-Gamma conjugate prior.
+#[derive(Clone, Copy, Debug)]
+struct RunStats {
+    n: f64,
+    mean: f64,
+    m2: f64,
+}
+
+impl RunStats {
+    fn new() -> RunStats {
+        RunStats {
+            n: 0.0,
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+
+    fn observe(&mut self, x: f64) {
+        self.n += 1.0;
+
+        let delta = x - self.mean;
+        self.mean += delta / self.n;
+        self.m2 += delta * (x - self.mean);
+    }
+}
+
+/// Bayesian online changepoint detector (BOCPD).
+///
+/// Maintains a discrete distribution over the run length \(r\) (the number
+/// of steps since the last changepoint) and updates it online from a stream
+/// of scalar observations (e.g. TD errors). Each run is modelled as a
+/// sequence of i.i.d. Normal observations under a Normal-Gamma conjugate
+/// prior, so the posterior predictive for any given run length is available
+/// in closed form as a Student-t density. The run-length vector is
+/// truncated to `max_run_length` entries, giving O(`max_run_length`)
+/// per-step cost.
+///
+/// # References
+/// - Adams, R. P., MacKay, D. J. C. (2007). Bayesian Online Changepoint
+/// Detection.
+pub struct ChangepointDetector {
+    hazard: f64,
+    threshold: f64,
+    max_run_length: usize,
+
+    mu0: f64,
+    kappa0: f64,
+    alpha0: f64,
+    beta0: f64,
+
+    run_length_probs: Vec<f64>,
+    run_stats: Vec<RunStats>,
+}
+
+impl ChangepointDetector {
+    /// Construct a detector with a standard normal Normal-Gamma prior
+    /// (`mu0 = 0`, `kappa0 = 1`, `alpha0 = 1`, `beta0 = 1`).
+    ///
+    /// `lambda` is the expected run length between changepoints (the hazard
+    /// rate is `1 / lambda`); `threshold` is the probability mass on
+    /// run-length zero above which a changepoint is signalled.
+    pub fn new(lambda: f64, threshold: f64, max_run_length: usize) -> ChangepointDetector {
+        ChangepointDetector::with_prior(lambda, threshold, max_run_length, 0.0, 1.0, 1.0, 1.0)
+    }
+
+    /// Construct a detector with an explicit Normal-Gamma prior over the
+    /// per-run mean and precision.
+    pub fn with_prior(
+        lambda: f64,
+        threshold: f64,
+        max_run_length: usize,
+        mu0: f64,
+        kappa0: f64,
+        alpha0: f64,
+        beta0: f64,
+    ) -> ChangepointDetector {
+        ChangepointDetector {
+            hazard: 1.0 / lambda,
+            threshold,
+            max_run_length,
+
+            mu0,
+            kappa0,
+            alpha0,
+            beta0,
+
+            run_length_probs: vec![1.0],
+            run_stats: vec![RunStats::new()],
+        }
+    }
+
+    /// Posterior predictive density of `x` under the run with the given
+    /// sufficient statistics (a Student-t density).
+    fn predictive(&self, stats: &RunStats, x: f64) -> f64 {
+        let kappa_n = self.kappa0 + stats.n;
+        let alpha_n = self.alpha0 + stats.n / 2.0;
+        let beta_n = self.beta0
+            + 0.5 * stats.m2
+            + (self.kappa0 * stats.n * (stats.mean - self.mu0).powi(2)) / (2.0 * kappa_n);
+
+        let dof = 2.0 * alpha_n;
+        let loc = (self.kappa0 * self.mu0 + stats.n * stats.mean) / kappa_n;
+        let scale = (beta_n * (kappa_n + 1.0) / (alpha_n * kappa_n)).sqrt();
+
+        student_t_pdf(x, dof, loc, scale)
+    }
+
+    /// Feed a new observation into the detector, returning `true` if a
+    /// changepoint is signalled (the posterior mass on run-length zero
+    /// exceeds `threshold`).
+    pub fn step(&mut self, x: f64) -> bool {
+        let n_runs = self.run_length_probs.len();
+
+        let mut grown_probs = vec![0.0; n_runs + 1];
+        let mut cp_mass = 0.0;
+
+        for r in 0..n_runs {
+            let pi_r = self.predictive(&self.run_stats[r], x);
+
+            grown_probs[r + 1] = self.run_length_probs[r] * pi_r * (1.0 - self.hazard);
+            cp_mass += self.run_length_probs[r] * pi_r * self.hazard;
+        }
+        grown_probs[0] = cp_mass;
+
+        let mut grown_stats = vec![RunStats::new(); n_runs + 1];
+        grown_stats[0].observe(x);
+
+        for r in 0..n_runs {
+            let mut stats = self.run_stats[r];
+            stats.observe(x);
+
+            grown_stats[r + 1] = stats;
+        }
+
+        grown_probs.truncate(self.max_run_length.max(1));
+        grown_stats.truncate(self.max_run_length.max(1));
+
+        let z: f64 = grown_probs.iter().sum();
+        if z > 0.0 {
+            grown_probs.iter_mut().for_each(|p| *p /= z);
+        }
+
+        self.run_length_probs = grown_probs;
+        self.run_stats = grown_stats;
+
+        self.run_length_probs[0] > self.threshold
+    }
+
+    /// The maximum-a-posteriori run length under the current distribution.
+    pub fn map_run_length(&self) -> usize {
+        self.run_length_probs
+            .iter()
+            .enumerate()
+            .fold((0, f64::MIN), |acc, (r, &p)| if p > acc.1 { (r, p) } else { acc })
+            .0
+    }
+}
+
+/// Natural-log of the gamma function via the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const COEFFS: [f64; 9] = [
+        0.999_999_999_999_809_93,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_571_6e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        // Reflection formula.
+        (PI / (PI * x).sin()).ln() - ln_gamma(1.0 - x)
+    } else {
+        let x = x - 1.0;
+        let mut a = COEFFS[0];
+        let t = x + G + 0.5;
+
+        for (i, c) in COEFFS.iter().enumerate().skip(1) {
+            a += c / (x + i as f64);
+        }
+
+        0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + a.ln()
+    }
+}
+
+fn student_t_pdf(x: f64, dof: f64, loc: f64, scale: f64) -> f64 {
+    let t = (x - loc) / scale;
+    let ln_pdf = ln_gamma((dof + 1.0) / 2.0)
+        - ln_gamma(dof / 2.0)
+        - 0.5 * (dof * PI).ln()
+        - scale.ln()
+        - (dof + 1.0) / 2.0 * (1.0 + t * t / dof).ln();
+
+    ln_pdf.exp()
+}
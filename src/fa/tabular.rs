@@ -0,0 +1,76 @@
+use core::Vector;
+use fa::{Approximator, EvaluationResult, QFunction};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Restricts greedy action selection and TD bootstrapping to a
+/// state-dependent subset of actions.
+///
+/// Implement this for domains (board games, tetrominoes, ...) where the set
+/// of legal actions varies by state, so that `Controller::pi`/`mu` and the
+/// bootstrap target in the update never range over an action the agent
+/// could not actually take.
+pub trait LegalActions<S> {
+    fn legal_actions(&self, s: &S) -> Vec<usize>;
+}
+
+/// The trivial `LegalActions` policy under which every one of `n_actions`
+/// actions is legal in every state.
+pub struct AllActions(pub usize);
+
+impl<S> LegalActions<S> for AllActions {
+    fn legal_actions(&self, _: &S) -> Vec<usize> { (0..self.0).collect() }
+}
+
+/// Exact tabular value function backed by a `HashMap<S, Vector<f64>>`.
+///
+/// Unlike `MultiLFA`/`SimpleLFA`, `Table` performs no projection: the state
+/// itself is the key. Unseen states evaluate to an all-zero row of length
+/// `n_actions`, and a row is stored for a state lazily the first time it is
+/// updated, so there is no need to enumerate the state space up front.
+pub struct Table<S: Hash + Eq> {
+    n_actions: usize,
+    values: HashMap<S, Vector<f64>>,
+}
+
+impl<S: Hash + Eq> Table<S> {
+    pub fn new(n_actions: usize) -> Self {
+        Table {
+            n_actions,
+            values: HashMap::new(),
+        }
+    }
+}
+
+impl<S: Clone + Hash + Eq> Table<S> {
+    fn row(&mut self, s: &S) -> &mut Vector<f64> {
+        let n_actions = self.n_actions;
+
+        self.values
+            .entry(s.clone())
+            .or_insert_with(|| Vector::zeros((n_actions,)))
+    }
+}
+
+impl<S: Clone + Hash + Eq> Approximator<S> for Table<S> {
+    type Value = Vector<f64>;
+
+    fn n_outputs(&self) -> usize { self.n_actions }
+
+    fn evaluate(&self, s: &S) -> EvaluationResult<Vector<f64>> {
+        Ok(self.values
+            .get(s)
+            .cloned()
+            .unwrap_or_else(|| Vector::zeros((self.n_actions,))))
+    }
+
+    fn update(&mut self, s: &S, errors: Vector<f64>) { *self.row(s) += &errors; }
+}
+
+impl<S: Clone + Hash + Eq> QFunction<S> for Table<S> {
+    fn evaluate_action(&self, s: &S, a: usize) -> f64 {
+        self.values.get(s).map_or(0.0, |row| row[a])
+    }
+
+    fn update_action(&mut self, s: &S, a: usize, error: f64) { self.row(s)[a] += error; }
+}
@@ -0,0 +1,152 @@
+//! Gradient-free direct policy search over perturbed weights.
+
+use crate::core::*;
+use crate::policies::ParameterisedPolicy;
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+use std::f64::consts::PI;
+
+/// Simulated-annealing optimiser over the raw weights of a
+/// `ParameterisedPolicy`.
+///
+/// Where the TD controllers in this crate follow a gradient (or
+/// semi-gradient) computed from a value estimate, `SimulatedAnnealing`
+/// searches weight space directly: each iteration perturbs the current
+/// weights with Gaussian noise scaled by the current temperature, scores
+/// the candidate by rolling out one or more episodes, and accepts it
+/// outright if it improves on the current return, or with probability
+/// `exp((return_candidate - return_current) / t)` if it doesn't. The
+/// temperature is cooled geometrically (`t <- t * cooling_rate`) each
+/// iteration. This gives a robust baseline for non-differentiable or
+/// highly non-convex reward landscapes where TD methods get stuck.
+///
+/// Supports multiple restarts and a fixed iteration budget per restart, as
+/// in classic annealing drivers. Each restart draws its own seed and
+/// begins from an independent point in weight space (the initial weights
+/// plus a fresh, large random perturbation), so restarts explore distinct
+/// basins rather than re-annealing the same incumbent; the best weights
+/// seen across all restarts are kept throughout.
+pub struct SimulatedAnnealing {
+    pub t0: f64,
+    pub cooling_rate: f64,
+    pub noise_scale: f64,
+
+    n_restarts: usize,
+    n_rollouts: usize,
+    max_iters: usize,
+}
+
+impl SimulatedAnnealing {
+    pub fn new(t0: f64, cooling_rate: f64) -> SimulatedAnnealing {
+        SimulatedAnnealing {
+            t0,
+            cooling_rate,
+            noise_scale: 1.0,
+
+            n_restarts: 1,
+            n_rollouts: 1,
+            max_iters: 1_000,
+        }
+    }
+
+    pub fn with_restarts(mut self, n_restarts: usize) -> Self {
+        self.n_restarts = n_restarts;
+        self
+    }
+
+    pub fn with_rollouts(mut self, n_rollouts: usize) -> Self {
+        self.n_rollouts = n_rollouts;
+        self
+    }
+
+    pub fn with_max_iters(mut self, max_iters: usize) -> Self {
+        self.max_iters = max_iters;
+        self
+    }
+
+    /// Search for the weights that maximise the mean return of `rollout`
+    /// (an episode closure that plays `policy` in the environment and
+    /// returns its total return), leaving `policy` holding the best weights
+    /// found across all restarts and returning them.
+    pub fn optimize<S, P>(
+        &self,
+        policy: &mut P,
+        mut rollout: impl FnMut(&mut P) -> f64,
+    ) -> Matrix<f64>
+    where
+        P: ParameterisedPolicy<S>,
+    {
+        let mut seed_rng = thread_rng();
+
+        let init_weights = policy.weights();
+        let mut best_weights = init_weights.clone();
+        let mut best_return = self.mean_return(policy, &mut rollout);
+
+        for _ in 0..self.n_restarts {
+            let mut rng = StdRng::seed_from_u64(seed_rng.gen::<u64>());
+
+            // Start this restart from an independent point in weight space
+            // rather than the incumbent best, so distinct restarts explore
+            // distinct basins instead of all hill-climbing the same one.
+            policy.update_raw(&init_weights - &policy.weights());
+            let restart_noise = self.sample_noise(init_weights.dim(), self.t0, &mut rng);
+            policy.update_raw(restart_noise);
+
+            let mut current_return = self.mean_return(policy, &mut rollout);
+            let mut t = self.t0;
+
+            if current_return > best_return {
+                best_return = current_return;
+                best_weights = policy.weights();
+            }
+
+            for _ in 0..self.max_iters {
+                let noise = self.sample_noise(policy.weights().dim(), t, &mut rng);
+
+                policy.update_raw(noise.clone());
+                let candidate_return = self.mean_return(policy, &mut rollout);
+
+                let accept = candidate_return > current_return
+                    || rng.gen::<f64>() < ((candidate_return - current_return) / t).exp();
+
+                if accept {
+                    current_return = candidate_return;
+
+                    if current_return > best_return {
+                        best_return = current_return;
+                        best_weights = policy.weights();
+                    }
+                } else {
+                    policy.update_raw(-noise);
+                }
+
+                t *= self.cooling_rate;
+            }
+        }
+
+        policy.update_raw(&best_weights - &policy.weights());
+
+        best_weights
+    }
+
+    fn mean_return<S, P: ParameterisedPolicy<S>>(
+        &self,
+        policy: &mut P,
+        rollout: &mut impl FnMut(&mut P) -> f64,
+    ) -> f64 {
+        (0..self.n_rollouts).map(|_| rollout(policy)).sum::<f64>() / self.n_rollouts as f64
+    }
+
+    fn sample_noise(&self, dim: (usize, usize), t: f64, rng: &mut impl Rng) -> Matrix<f64> {
+        let sigma = self.noise_scale * t.sqrt();
+
+        Matrix::from_shape_fn(dim, |_| sigma * standard_normal(rng))
+    }
+}
+
+/// Sample a standard normal variate via the Box-Muller transform.
+fn standard_normal(rng: &mut impl Rng) -> f64 {
+    let u1: f64 = rng.gen::<f64>().max(::std::f64::EPSILON);
+    let u2: f64 = rng.gen::<f64>();
+
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
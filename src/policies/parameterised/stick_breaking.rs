@@ -0,0 +1,175 @@
+use crate::{
+    core::*,
+    domains::Transition,
+    fa::{Parameterised, QFunction},
+    policies::{sample_probs_with_rng, DifferentiablePolicy, FinitePolicy, ParameterisedPolicy, Policy},
+    utils::argmax_choose,
+};
+use rand::{rngs::ThreadRng, thread_rng};
+use std::f64;
+
+#[inline(always)]
+fn sigmoid(x: f64) -> f64 { 1.0 / (1.0 + (-x).exp()) }
+
+/// Stick-breaking nonparametric softmax policy.
+///
+/// `Boltzmann` and `TruncatedBoltzmann` assume a fixed, finite action set
+/// governed by a single temperature. `StickBreakingPolicy` instead
+/// represents the action distribution as a (truncated)
+/// Dirichlet-process-style stick-breaking construction: stick `k` has
+/// proportion `v_k`, and the probability mass assigned to action `k` is
+/// `pi_k = v_k * prod_{j<k}(1 - v_j)`, with the final stick forced to
+/// consume all remaining mass (`v_{K-1} = 1`) so the truncated vector still
+/// sums to one.
+///
+/// Each stick's proportion is driven by a learned logit combined with the
+/// corresponding Q-value, `v_k = sigmoid((logit_k + q_k) / alpha)`, so the
+/// policy can be trained like any other `ParameterisedPolicy`. `alpha`
+/// plays the role of the Dirichlet-process concentration parameter: a
+/// small `alpha` sharpens the sigmoid, pushing each `v_k` towards 0 or 1 so
+/// that mass concentrates on the first stick whose `logit_k + q_k` is
+/// positive (or on the last stick if there is none), while a large `alpha`
+/// flattens it, pushing every `v_k` towards 1/2 so that the weights approach
+/// the geometric sequence 1/2, 1/4, ... with the last stick taking the
+/// remainder. `alpha` anneals over episodes via the usual
+/// `handle_terminal` step mechanism.
+///
+/// # References
+/// - Sethuraman, J. (1994). A constructive definition of Dirichlet priors.
+/// Statistica Sinica, 4(2):639-650.
+pub struct StickBreakingPolicy<Q> {
+    q_func: Q,
+
+    logits: Vector<f64>,
+    alpha: Parameter,
+
+    rng: ThreadRng,
+}
+
+impl<Q> StickBreakingPolicy<Q> {
+    pub fn new<T: Into<Parameter>>(q_func: Q, n_sticks: usize, alpha: T) -> Self {
+        StickBreakingPolicy {
+            q_func,
+
+            logits: Vector::zeros((n_sticks,)),
+            alpha: alpha.into(),
+
+            rng: thread_rng(),
+        }
+    }
+
+    /// Compute the stick proportions `v_k`, forcing the last component to
+    /// absorb all remaining probability mass.
+    fn proportions(&self, qs: &Vector<f64>) -> Vector<f64> {
+        let alpha = self.alpha.value();
+        let n = self.logits.len();
+
+        (0..n)
+            .map(|k| {
+                if k == n - 1 {
+                    1.0
+                } else {
+                    sigmoid((self.logits[k] + qs[k]) / alpha)
+                }
+            })
+            .collect()
+    }
+
+    /// Materialise the truncated stick-breaking distribution `pi_k = v_k *
+    /// prod_{j<k}(1 - v_j)`.
+    fn stick_breaking_probabilities(&self, qs: &Vector<f64>) -> Vector<f64> {
+        let vs = self.proportions(qs);
+        let mut remaining = 1.0;
+
+        vs.iter()
+            .map(|&v| {
+                let pi = remaining * v;
+                remaining *= 1.0 - v;
+
+                pi
+            })
+            .collect()
+    }
+}
+
+impl<Q> Algorithm for StickBreakingPolicy<Q> {
+    fn handle_terminal(&mut self) { self.alpha = self.alpha.step(); }
+}
+
+impl<S, Q: QFunction<S>> Policy<S> for StickBreakingPolicy<Q> {
+    type Action = usize;
+
+    fn sample(&mut self, s: &S) -> usize {
+        let ps = self.probabilities(s);
+
+        sample_probs_with_rng(&mut self.rng, ps.as_slice().unwrap())
+    }
+
+    fn mpa(&mut self, s: &S) -> usize {
+        let ps = self.probabilities(s);
+
+        argmax_choose(&mut self.rng, ps.as_slice().unwrap()).1
+    }
+
+    fn probability(&mut self, s: &S, a: usize) -> f64 { self.probabilities(s)[a] }
+}
+
+impl<S, Q: QFunction<S>> FinitePolicy<S> for StickBreakingPolicy<Q> {
+    fn n_actions(&self) -> usize { self.logits.len() }
+
+    fn probabilities(&mut self, s: &S) -> Vector<f64> {
+        let qs = self.q_func.evaluate(&self.q_func.to_features(s)).unwrap();
+
+        self.stick_breaking_probabilities(&qs)
+    }
+}
+
+impl<S, Q: QFunction<S>> DifferentiablePolicy<S> for StickBreakingPolicy<Q> {
+    fn grad_log(&self, s: &S, a: usize) -> Matrix<f64> {
+        let qs = self.q_func.evaluate(&self.q_func.to_features(s)).unwrap();
+        let vs = self.proportions(&qs);
+        let n = self.logits.len();
+        let alpha = self.alpha.value();
+
+        Matrix::from_shape_fn((n, 1), |(k, _)| {
+            // d(log pi_a)/d(v_k), zero unless k == a or k < a.
+            let d_log_pi_d_v = if k == a {
+                if a == n - 1 { 0.0 } else { 1.0 / vs[a] }
+            } else if k < a {
+                -1.0 / (1.0 - vs[k])
+            } else {
+                0.0
+            };
+
+            // d(v_k)/d(logit_k) for the sigmoid parameterisation; the final
+            // stick is pinned at v = 1 and contributes no gradient.
+            let d_v_d_logit = if k == n - 1 {
+                0.0
+            } else {
+                vs[k] * (1.0 - vs[k]) / alpha
+            };
+
+            d_log_pi_d_v * d_v_d_logit
+        })
+    }
+}
+
+impl<Q> Parameterised for StickBreakingPolicy<Q> {
+    fn weights(&self) -> Matrix<f64> {
+        let n = self.logits.len();
+
+        Matrix::from_shape_fn((n, 1), |(k, _)| self.logits[k])
+    }
+}
+
+impl<S, Q: QFunction<S>> ParameterisedPolicy<S> for StickBreakingPolicy<Q> {
+    fn update(&mut self, s: &S, a: usize, error: f64) {
+        let grad = self.grad_log(s, a);
+
+        self.logits.scaled_add(error, &grad.column(0));
+    }
+
+    fn update_raw(&mut self, errors: Matrix<f64>) {
+        self.logits += &errors.column(0);
+    }
+}
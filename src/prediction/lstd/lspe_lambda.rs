@@ -0,0 +1,164 @@
+use core::*;
+use core::aitken::ConvergentSequence;
+use domains::Transition;
+use fa::{Approximator, VFunction, Parameterised, Projector, Projection, SimpleLFA};
+use ndarray::Axis;
+
+/// LSPE(lambda): an iterative, Aitken-accelerated alternative to
+/// `LSTDLambda`.
+///
+/// `LSTDLambda` re-solves `A^-1 b` from scratch every batch, which is O(d^3)
+/// and throws away the fact that consecutive batches produce a
+/// slowly-changing weight vector. `LSPELambda` instead refines
+/// `fa_theta.weights` with fixed-point iterations of the Richardson map `w
+/// <- w + rate*(b - A*w)`, and accelerates the resulting sequence with
+/// Aitken's delta-squared method so that it converges in far fewer
+/// iterations than the raw fixed-point map, without ever falling back to a
+/// pseudo-inverse.
+///
+/// # References
+/// - Nedić, A., Bertsekas, D. P. (2003). Least Squares Policy Evaluation
+/// Algorithms with Linear Function Approximation. Discrete Event Dynamic
+/// Systems, 13(1-2):79-110.
+pub struct LSPELambda<S, P: Projector<S>> {
+    pub fa_theta: Shared<SimpleLFA<S, P>>,
+
+    pub gamma: Parameter,
+    pub rate: Parameter,
+
+    tol: f64,
+    max_iters: usize,
+
+    trace: Trace,
+
+    n_samples: usize,
+    a: Matrix<f64>,
+    b: Vector<f64>,
+}
+
+impl<S, P: Projector<S>> LSPELambda<S, P> {
+    pub fn new<T1, T2>(
+        fa_theta: Shared<SimpleLFA<S, P>>,
+        trace: Trace,
+        gamma: T1,
+        rate: T2,
+        tol: f64,
+        max_iters: usize,
+    ) -> Self
+    where
+        T1: Into<Parameter>,
+        T2: Into<Parameter>,
+    {
+        let n_features = fa_theta.borrow().projector.dim();
+
+        LSPELambda {
+            fa_theta,
+
+            gamma: gamma.into(),
+            rate: rate.into(),
+
+            tol,
+            max_iters,
+
+            trace,
+
+            n_samples: 0,
+            a: Matrix::zeros((n_features, n_features)),
+            b: Vector::zeros((n_features,)),
+        }
+    }
+}
+
+impl<S, P: Projector<S>> LSPELambda<S, P> {
+    #[inline(always)]
+    fn compute_dense_fv(&self, s: &S) -> Vector<f64> {
+        self.fa_theta.borrow().projector.project(s).expanded(self.a.rows())
+    }
+
+    #[inline]
+    fn update_trace(&mut self, phi: &Vector<f64>) -> Vector<f64> {
+        let decay_rate = self.trace.lambda.value() * self.gamma.value();
+
+        self.trace.decay(decay_rate);
+        self.trace.update(phi);
+
+        self.trace.get()
+    }
+
+    /// Refine `fa_theta.weights` via Aitken-accelerated fixed-point
+    /// iteration of `w <- w + rate*(b - A*w)` rather than a direct solve.
+    ///
+    /// `A` and `b` are normalized by the number of samples seen so far
+    /// before the fixed-point map is applied, so the effective step size
+    /// `rate * ||A||` stays O(1) regardless of how much data the agent has
+    /// processed (rather than growing with the batch count and pushing the
+    /// Richardson iteration past its convergence radius).
+    pub fn solve(&mut self) {
+        let w0 = self.fa_theta.borrow().approximator.weights.clone();
+        let rate = self.rate.value();
+        let n = (self.n_samples as f64).max(1.0);
+        let a = &self.a / n;
+        let b = &self.b / n;
+
+        let max_iters = self.max_iters;
+        let iterates = (0..max_iters).scan(w0, move |w, _| {
+            *w = &*w + rate * (&b - a.dot(w));
+
+            Some(w.clone())
+        });
+
+        if let Some(w_star) = iterates.aitken_accelerate(self.tol).last() {
+            self.fa_theta.borrow_mut().approximator.weights.assign(&w_star);
+        }
+    }
+}
+
+impl<S, P: Projector<S>> Algorithm for LSPELambda<S, P> {
+    fn handle_terminal(&mut self) {
+        self.gamma = self.gamma.step();
+        self.rate = self.rate.step();
+    }
+}
+
+impl<S, A, P: Projector<S>> BatchLearner<S, A> for LSPELambda<S, P> {
+    fn handle_batch(&mut self, ts: &[Transition<S, A>]) {
+        ts.into_iter().for_each(|t| {
+            let (s, ns) = (t.from.state(), t.to.state());
+
+            self.n_samples += 1;
+
+            let phi_s = self.compute_dense_fv(s);
+            let z = self.update_trace(&phi_s);
+
+            self.b.scaled_add(t.reward, &z);
+
+            let pd = if t.terminated() {
+                self.trace.decay(0.0);
+
+                phi_s
+            } else {
+                let phi_ns = self.compute_dense_fv(ns);
+
+                phi_s - self.gamma.value()*phi_ns
+            }.insert_axis(Axis(0));
+
+            self.a += &z.insert_axis(Axis(1)).dot(&pd);
+        });
+
+        self.solve();
+    }
+}
+
+impl<S, P: Projector<S>> ValuePredictor<S> for LSPELambda<S, P> {
+    fn predict_v(&mut self, s: &S) -> f64 {
+        self.fa_theta.borrow().evaluate(s).unwrap()
+    }
+}
+
+impl<S, A, P: Projector<S>> ActionValuePredictor<S, A> for LSPELambda<S, P> {}
+
+impl<S, P: Projector<S>> Parameterised for LSPELambda<S, P> {
+    fn weights(&self) -> Matrix<f64> {
+        self.fa_theta.borrow().weights()
+    }
+}
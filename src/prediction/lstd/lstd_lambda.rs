@@ -1,4 +1,5 @@
 use core::*;
+use core::changepoint::ChangepointDetector;
 use domains::Transition;
 use fa::{Approximator, VFunction, Parameterised, Projector, Projection, SimpleLFA};
 use geometry::Space;
@@ -12,6 +13,7 @@ pub struct LSTDLambda<S, P: Projector<S>> {
     pub gamma: Parameter,
 
     trace: Trace,
+    changepoint: Option<ChangepointDetector>,
 
     a: Matrix<f64>,
     b: Vector<f64>,
@@ -29,11 +31,21 @@ impl<S, P: Projector<S>> LSTDLambda<S, P> {
             gamma: gamma.into(),
 
             trace,
+            changepoint: None,
 
             a: Matrix::zeros((n_features, n_features)),
             b: Vector::zeros((n_features,)),
         }
     }
+
+    /// Attach a Bayesian online changepoint detector that watches the TD
+    /// error stream and resets the trace and the accumulated `A`/`b`
+    /// sufficient statistics whenever it signals a shift in the underlying
+    /// MDP.
+    pub fn with_changepoint_detector(mut self, detector: ChangepointDetector) -> Self {
+        self.changepoint = Some(detector);
+        self
+    }
 }
 
 impl<S, P: Projector<S>> LSTDLambda<S, P> {
@@ -77,6 +89,22 @@ impl<S, A, P: Projector<S>> BatchLearner<S, A> for LSTDLambda<S, P> {
         ts.into_iter().for_each(|t| {
             let (s, ns) = (t.from.state(), t.to.state());
 
+            if let Some(ref mut cpd) = self.changepoint {
+                let v_s = self.fa_theta.borrow().evaluate(s).unwrap_or(0.0);
+                let v_ns = if t.terminated() {
+                    0.0
+                } else {
+                    self.fa_theta.borrow().evaluate(ns).unwrap_or(0.0)
+                };
+                let td_error = t.reward + self.gamma.value() * v_ns - v_s;
+
+                if cpd.step(td_error) {
+                    self.trace.decay(0.0);
+                    self.a.fill(0.0);
+                    self.b.fill(0.0);
+                }
+            }
+
             let phi_s = self.compute_dense_fv(s);
             let z = self.update_trace(&phi_s);
 